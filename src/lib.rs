@@ -2,7 +2,11 @@ use rust_code_obfuscator::{obfuscate_flow, obfuscate_string};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use wasm_bindgen::prelude::*;
-use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement, WebGlRenderingContext};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    window, AudioBuffer, CanvasRenderingContext2d, DynamicsCompressorNode, HtmlCanvasElement,
+    OfflineAudioContext, OscillatorNode, OscillatorType, WebGlRenderingContext,
+};
 
 /// Fingerprint data structure containing all collected browser information
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -47,10 +51,17 @@ pub struct Fingerprint {
     pub webgl_version: String,
     pub webgl_shading_language_version: String,
     pub webgl_extensions: Vec<String>,
+    pub webgl_parameters: Vec<(String, String)>,
 
     // Audio fingerprint
     pub audio_fingerprint: String,
 
+    // Detected fonts
+    pub fonts: Vec<String>,
+
+    // Anti-fingerprinting noise injection
+    pub noise: NoiseReport,
+
     // Plugins & MIME types
     pub plugins: Vec<String>,
     pub mime_types: Vec<String>,
@@ -58,8 +69,123 @@ pub struct Fingerprint {
     // Connection info
     pub online: bool,
 
-    // Final hash
+    // Final hashes: full identity and the version-tolerant stable identity.
     pub fingerprint_hash: String,
+    pub stable_hash: String,
+}
+
+/// Whether anti-fingerprinting extensions are injecting per-read randomized
+/// noise, broken down by surface. When a surface is noisy the same drawing
+/// yields different bytes on each read, so its contribution is unstable.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NoiseReport {
+    pub canvas_noise: bool,
+    pub audio_noise: bool,
+    pub noise_detected: bool,
+}
+
+/// Individual automation tells plus an aggregate 0–100 bot-likelihood score.
+/// Lets consumers distinguish real browsers from headless/driven ones rather
+/// than relying on the hashed attributes alone.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BotSignals {
+    pub webdriver: bool,
+    pub missing_chrome: bool,
+    pub headless_ua: bool,
+    pub no_plugins_desktop: bool,
+    pub empty_languages: bool,
+    pub swiftshader_renderer: bool,
+    pub permissions_inconsistency: bool,
+    pub score: u32,
+}
+
+/// Selectable fingerprint components, used to canonicalize a chosen subset of
+/// signals in a fixed order before hashing. New signals can be added to the
+/// identity by listing them here rather than editing the hash routine inline.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashComponent {
+    UserAgent,
+    Language,
+    Platform,
+    HardwareConcurrency,
+    Screen,
+    AvailScreen,
+    DevicePixelRatio,
+    Timezone,
+    TimezoneOffset,
+    Online,
+    Canvas,
+    WebglVendor,
+    WebglRenderer,
+    WebglParameters,
+    Fonts,
+    Audio,
+}
+
+/// The fixed master order in which components are serialized. Canonicalization
+/// always walks this order and emits only the selected components, so adding a
+/// component or toggling one on/off does not reshuffle the rest of the string.
+const COMPONENT_ORDER: [HashComponent; 16] = [
+    HashComponent::UserAgent,
+    HashComponent::Language,
+    HashComponent::Platform,
+    HashComponent::HardwareConcurrency,
+    HashComponent::Screen,
+    HashComponent::AvailScreen,
+    HashComponent::DevicePixelRatio,
+    HashComponent::Timezone,
+    HashComponent::TimezoneOffset,
+    HashComponent::Online,
+    HashComponent::Canvas,
+    HashComponent::WebglVendor,
+    HashComponent::WebglRenderer,
+    HashComponent::WebglParameters,
+    HashComponent::Fonts,
+    HashComponent::Audio,
+];
+
+/// Whether to hash every [`HashComponent`] (`Full`) or only the high-entropy,
+/// low-churn ones that persist across sessions (`Stable`). Collected fields
+/// with no `HashComponent` (e.g. plugins, storage flags) are never hashed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashMode {
+    Stable,
+    #[default]
+    Full,
+}
+
+/// Configuration for [`compute_hash`]. When `components` is `None` the set is
+/// derived from `mode`; an explicit list overrides the mode's default.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct HashConfig {
+    #[serde(default)]
+    pub mode: HashMode,
+    #[serde(default)]
+    pub components: Option<Vec<HashComponent>>,
+}
+
+impl HashConfig {
+    /// Resolve the effective component set for this config.
+    fn resolved_components(&self) -> Vec<HashComponent> {
+        if let Some(components) = &self.components {
+            return components.clone();
+        }
+        match self.mode {
+            HashMode::Full => COMPONENT_ORDER.to_vec(),
+            // High-entropy, low-churn signals only: exclude volatile attributes
+            // such as the user agent (changes on every browser auto-update),
+            // online status, and available screen dimensions.
+            HashMode::Stable => vec![
+                HashComponent::Timezone,
+                HashComponent::Canvas,
+                HashComponent::WebglVendor,
+                HashComponent::WebglRenderer,
+                HashComponent::WebglParameters,
+                HashComponent::Fonts,
+                HashComponent::Audio,
+            ],
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -79,7 +205,7 @@ impl BrowserFingerprinter {
 
     /// Collect all fingerprint data
     #[wasm_bindgen]
-    pub fn collect(&mut self) -> Result<String, JsValue> {
+    pub async fn collect(&mut self) -> Result<String, JsValue> {
         obfuscate_flow!();
         let window = window().ok_or("No window object")?;
         obfuscate_flow!();
@@ -130,11 +256,22 @@ impl BrowserFingerprinter {
             webgl_version,
             webgl_shading_language_version,
             webgl_extensions,
+            webgl_parameters,
         ) = get_webgl_info(&document)?;
 
         // Audio fingerprint
         obfuscate_flow!();
-        let audio_fingerprint = "audio-context-available".to_string();
+        let audio_fingerprint = generate_audio_fingerprint()
+            .await
+            .unwrap_or_else(|_| "audio-context-unavailable".to_string());
+
+        // Detected fonts
+        obfuscate_flow!();
+        let fonts = detect_fonts(&document).unwrap_or_default();
+
+        // Anti-fingerprinting noise injection
+        obfuscate_flow!();
+        let noise = detect_randomization(&document).await.unwrap_or_default();
 
         // Plugins & MIME types
         obfuscate_flow!();
@@ -173,15 +310,26 @@ impl BrowserFingerprinter {
             webgl_version,
             webgl_shading_language_version,
             webgl_extensions,
+            webgl_parameters,
             audio_fingerprint,
+            fonts,
+            noise,
             plugins,
             mime_types,
             online,
             fingerprint_hash: String::new(),
+            stable_hash: String::new(),
         };
 
-        // Generate final hash
-        fingerprint.fingerprint_hash = generate_hash(&fingerprint);
+        // Generate the full and stable identities.
+        fingerprint.fingerprint_hash = compute_hash(&fingerprint, &HashConfig::default());
+        fingerprint.stable_hash = compute_hash(
+            &fingerprint,
+            &HashConfig {
+                mode: HashMode::Stable,
+                components: None,
+            },
+        );
 
         let json = serde_json::to_string_pretty(&fingerprint)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
@@ -196,6 +344,167 @@ impl BrowserFingerprinter {
     pub fn get_hash(&self) -> Option<String> {
         self.fingerprint.as_ref().map(|f| f.fingerprint_hash.clone())
     }
+
+    /// Collect the fingerprint and hash it under a caller-supplied [`HashConfig`]
+    /// (JSON). Returns the fingerprint JSON with `fingerprint_hash` set to the
+    /// full identity and `stable_hash` to the hash produced by `config`, letting
+    /// downstream users trade uniqueness against persistence across sessions.
+    #[wasm_bindgen]
+    pub async fn collect_with_config(&mut self, config_json: &str) -> Result<String, JsValue> {
+        obfuscate_flow!();
+        let config: HashConfig = serde_json::from_str(config_json).unwrap_or_default();
+
+        // Reuse the standard collection, then re-hash under the requested config.
+        self.collect().await?;
+        let mut fingerprint = self
+            .fingerprint
+            .clone()
+            .ok_or("Fingerprint collection failed")?;
+
+        fingerprint.stable_hash = compute_hash(&fingerprint, &config);
+
+        let json = serde_json::to_string_pretty(&fingerprint)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.fingerprint = Some(fingerprint);
+        Ok(json)
+    }
+
+    /// Probe for well-known automation/headless tells and return the individual
+    /// flags together with an aggregate 0–100 bot-likelihood score as JSON.
+    #[wasm_bindgen]
+    pub async fn detect_automation(&self) -> Result<String, JsValue> {
+        obfuscate_flow!();
+        let window = window().ok_or("No window object")?;
+        let navigator = window.navigator();
+        let document = window.document().ok_or("No document object")?;
+
+        let user_agent = navigator.user_agent().unwrap_or_default();
+
+        // navigator.webdriver === true
+        let webdriver =
+            js_sys::Reflect::get(&navigator, &JsValue::from_str(&obfuscate_string!("webdriver")))
+                .ok()
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+        // window.chrome is present on real Chrome but commonly absent when driven.
+        let missing_chrome =
+            js_sys::Reflect::get(&window, &JsValue::from_str(&obfuscate_string!("chrome")))
+                .map(|v| v.is_undefined() || v.is_null())
+                .unwrap_or(true);
+
+        // "HeadlessChrome" marker left in the user agent.
+        let headless_ua = user_agent.contains(&obfuscate_string!("HeadlessChrome"));
+
+        // A desktop UA with no plugins and no MIME types is a classic headless tell.
+        let is_desktop = !(user_agent.contains("Mobi")
+            || user_agent.contains("Android")
+            || user_agent.contains("iPhone"));
+        let no_plugins = get_plugins(&navigator).is_empty() && get_mime_types(&navigator).is_empty();
+        let no_plugins_desktop = is_desktop && no_plugins;
+
+        // Empty navigator.languages.
+        let empty_languages = get_languages(&navigator).is_empty();
+
+        // Software renderers used by headless contexts.
+        let swiftshader_renderer = get_webgl_info(&document)
+            .map(|(_, renderer, _, _, _, _)| {
+                renderer.contains("SwiftShader")
+                    || renderer.contains("llvmpipe")
+                    || renderer.contains("Google")
+            })
+            .unwrap_or(false);
+
+        // Notification.permission === "denied" while the permissions API resolves
+        // the same query to "prompt" is inconsistent with a real browser.
+        let permissions_inconsistency = check_permissions_inconsistency(&window, &navigator)
+            .await
+            .unwrap_or(false);
+
+        let mut signals = BotSignals {
+            webdriver,
+            missing_chrome,
+            headless_ua,
+            no_plugins_desktop,
+            empty_languages,
+            swiftshader_renderer,
+            permissions_inconsistency,
+            score: 0,
+        };
+        signals.score = score_bot_signals(&signals);
+
+        serde_json::to_string_pretty(&signals).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+/// Weight each tell and clamp the total to a 0–100 score.
+fn score_bot_signals(signals: &BotSignals) -> u32 {
+    let mut score = 0u32;
+    if signals.webdriver {
+        score += 40;
+    }
+    if signals.headless_ua {
+        score += 30;
+    }
+    if signals.permissions_inconsistency {
+        score += 20;
+    }
+    if signals.swiftshader_renderer {
+        score += 15;
+    }
+    if signals.missing_chrome {
+        score += 10;
+    }
+    if signals.no_plugins_desktop {
+        score += 10;
+    }
+    if signals.empty_languages {
+        score += 5;
+    }
+    score.min(100)
+}
+
+/// Resolve `navigator.permissions.query({name:'notifications'})` and compare its
+/// state against `Notification.permission`. A "denied"/"prompt" mismatch is a tell.
+async fn check_permissions_inconsistency(
+    window: &web_sys::Window,
+    navigator: &web_sys::Navigator,
+) -> Result<bool, JsValue> {
+    obfuscate_flow!();
+    // Notification.permission
+    let notification =
+        js_sys::Reflect::get(window, &JsValue::from_str(&obfuscate_string!("Notification")))?;
+    if notification.is_undefined() || notification.is_null() {
+        return Ok(false);
+    }
+    let notification_permission =
+        js_sys::Reflect::get(&notification, &JsValue::from_str(&obfuscate_string!("permission")))?
+            .as_string()
+            .unwrap_or_default();
+
+    // navigator.permissions.query({ name: "notifications" })
+    let permissions =
+        js_sys::Reflect::get(navigator, &JsValue::from_str(&obfuscate_string!("permissions")))?;
+    if permissions.is_undefined() || permissions.is_null() {
+        return Ok(false);
+    }
+    let query = js_sys::Reflect::get(&permissions, &JsValue::from_str(&obfuscate_string!("query")))?
+        .dyn_into::<js_sys::Function>()?;
+
+    let descriptor = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &descriptor,
+        &JsValue::from_str(&obfuscate_string!("name")),
+        &JsValue::from_str(&obfuscate_string!("notifications")),
+    )?;
+
+    let promise: js_sys::Promise = query.call1(&permissions, &descriptor)?.dyn_into()?;
+    let status = JsFuture::from(promise).await?;
+    let state = js_sys::Reflect::get(&status, &JsValue::from_str(&obfuscate_string!("state")))?
+        .as_string()
+        .unwrap_or_default();
+
+    Ok(notification_permission == "denied" && state == "prompt")
 }
 
 fn get_languages(navigator: &web_sys::Navigator) -> Vec<String> {
@@ -275,7 +584,12 @@ fn check_indexed_db() -> bool {
     }
 }
 
-fn generate_canvas_fingerprint(document: &web_sys::Document) -> Result<String, JsValue> {
+/// Render the fixed canvas fingerprint drawing into a fresh canvas and return
+/// it. Shared by the fingerprint hash and the noise-detection double-render so
+/// both exercise byte-for-byte identical output.
+fn render_canvas_fingerprint(
+    document: &web_sys::Document,
+) -> Result<HtmlCanvasElement, JsValue> {
     obfuscate_flow!();
     let canvas: HtmlCanvasElement = document
         .create_element("canvas")?
@@ -316,6 +630,12 @@ fn generate_canvas_fingerprint(document: &web_sys::Document) -> Result<String, J
     context.set_fill_style_str("#3399ff");
     context.fill();
 
+    Ok(canvas)
+}
+
+fn generate_canvas_fingerprint(document: &web_sys::Document) -> Result<String, JsValue> {
+    let canvas = render_canvas_fingerprint(document)?;
+
     // Get canvas data and hash it
     let data_url = canvas.to_data_url()?;
     let mut hasher = Sha256::new();
@@ -325,9 +645,166 @@ fn generate_canvas_fingerprint(document: &web_sys::Document) -> Result<String, J
     Ok(hex::encode(result))
 }
 
+/// Infer the set of installed fonts by measuring rendered text dimensions
+/// against the three generic baselines. When a candidate font is installed the
+/// browser renders with it instead of falling back to the baseline family,
+/// shifting the measured width or height; a difference against any baseline
+/// marks it as present. Fonts are one of the highest-entropy browser attributes.
+fn detect_fonts(document: &web_sys::Document) -> Result<Vec<String>, JsValue> {
+    obfuscate_flow!();
+    const PROBE: &str = "mmmmmmmmmmlli";
+    const SIZE: &str = "72px";
+    let baselines = ["serif", "sans-serif", "monospace"];
+    let candidates = [
+        "Arial",
+        "Times New Roman",
+        "Courier New",
+        "Helvetica",
+        "Georgia",
+        "Comic Sans MS",
+        "Verdana",
+        "Tahoma",
+        "Trebuchet MS",
+        "Impact",
+        "Arial Black",
+        "Palatino Linotype",
+    ];
+
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()?;
+    let context = canvas
+        .get_context("2d")?
+        .ok_or("Failed to get 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // Rendered width and height of the probe string for each generic baseline
+    // family. Height is derived from the ascent/descent bounding box.
+    let measure = |ctx: &CanvasRenderingContext2d| -> Result<(f64, f64), JsValue> {
+        let metrics = ctx.measure_text(PROBE)?;
+        let height = metrics.actual_bounding_box_ascent() + metrics.actual_bounding_box_descent();
+        Ok((metrics.width(), height))
+    };
+
+    let mut baseline_metrics = Vec::with_capacity(baselines.len());
+    for baseline in baselines {
+        context.set_font(&format!("{} {}", SIZE, baseline));
+        baseline_metrics.push(measure(&context)?);
+    }
+
+    let mut detected = Vec::new();
+    for candidate in candidates {
+        let mut installed = false;
+        for (i, baseline) in baselines.iter().enumerate() {
+            context.set_font(&format!("{} '{}', {}", SIZE, candidate, baseline));
+            let (width, height) = measure(&context)?;
+            let (base_width, base_height) = baseline_metrics[i];
+            if (width - base_width).abs() > f64::EPSILON
+                || (height - base_height).abs() > f64::EPSILON
+            {
+                installed = true;
+                break;
+            }
+        }
+        if installed {
+            detected.push(candidate.to_string());
+        }
+    }
+
+    Ok(detected)
+}
+
+/// Compute an audio-stack fingerprint by rendering a fixed oscillator through a
+/// dynamics compressor in an `OfflineAudioContext`. The compressor's
+/// platform-specific rounding makes the rendered samples a strong discriminator.
+async fn generate_audio_fingerprint() -> Result<String, JsValue> {
+    obfuscate_flow!();
+    let context = OfflineAudioContext::new_with_number_of_channels_and_length_and_sample_rate(
+        1, 44100, 44100.0,
+    )?;
+
+    obfuscate_flow!();
+    let oscillator: OscillatorNode = context.create_oscillator()?;
+    oscillator.set_type(OscillatorType::Triangle);
+    oscillator.frequency().set_value(10000.0);
+
+    let compressor: DynamicsCompressorNode = context.create_dynamics_compressor()?;
+    compressor.threshold().set_value(-50.0);
+    compressor.knee().set_value(40.0);
+    compressor.ratio().set_value(12.0);
+    compressor.attack().set_value(0.0);
+    compressor.release().set_value(0.25);
+
+    oscillator.connect_with_audio_node(&compressor)?;
+    compressor.connect_with_audio_node(&context.destination())?;
+
+    oscillator.start()?;
+    obfuscate_flow!();
+    let rendered = JsFuture::from(context.start_rendering()?).await?;
+    let buffer: AudioBuffer = rendered.dyn_into()?;
+
+    // Sum the absolute values of a stable slice of the rendered channel.
+    let channel = buffer.get_channel_data(0)?;
+    let sum: f64 = channel
+        .iter()
+        .skip(4500)
+        .take(500)
+        .map(|sample| (*sample as f64).abs())
+        .sum();
+
+    let mut hasher = Sha256::new();
+    hasher.update(sum.to_string().as_bytes());
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Collected WebGL identity: vendor, renderer, version, shading-language
+/// version, supported extensions, and the enumerated numeric/precision
+/// parameters.
+type WebGlInfo = (
+    String,
+    String,
+    String,
+    String,
+    Vec<String>,
+    Vec<(String, String)>,
+);
+
+/// Detect per-read randomized noise injected by anti-fingerprinting extensions.
+/// For the canvas, the identical drawing is rendered into two independent
+/// canvases and a single canvas is read twice; for audio, the fixed render is
+/// computed twice. Any divergence across identical reads means randomization is
+/// active, so the affected surface should not be trusted as stable.
+async fn detect_randomization(document: &web_sys::Document) -> Result<NoiseReport, JsValue> {
+    obfuscate_flow!();
+    // Two independent canvases with the identical drawing.
+    let first = render_canvas_fingerprint(document)?.to_data_url()?;
+    let second = render_canvas_fingerprint(document)?.to_data_url()?;
+
+    // Same canvas, read twice without redrawing.
+    let single = render_canvas_fingerprint(document)?;
+    let read_a = single.to_data_url()?;
+    let read_b = single.to_data_url()?;
+
+    let canvas_noise = first != second || read_a != read_b;
+
+    // Audio double-render check.
+    let audio_a = generate_audio_fingerprint().await.ok();
+    let audio_b = generate_audio_fingerprint().await.ok();
+    let audio_noise = match (audio_a, audio_b) {
+        (Some(a), Some(b)) => a != b,
+        _ => false,
+    };
+
+    Ok(NoiseReport {
+        canvas_noise,
+        audio_noise,
+        noise_detected: canvas_noise || audio_noise,
+    })
+}
+
 fn get_webgl_info(
     document: &web_sys::Document,
-) -> Result<(String, String, String, String, Vec<String>), JsValue> {
+) -> Result<WebGlInfo, JsValue> {
     obfuscate_flow!();
     let canvas: HtmlCanvasElement = document
         .create_element("canvas")?
@@ -359,12 +836,13 @@ fn get_webgl_info(
         "Not available".to_string(),
         "Not available".to_string(),
         vec![],
+        vec![],
     ))
 }
 
 fn get_webgl1_info(
     gl: WebGlRenderingContext,
-) -> Result<(String, String, String, String, Vec<String>), JsValue> {
+) -> Result<WebGlInfo, JsValue> {
     // Try to get the debug info extension
     let debug_info = gl.get_extension("WEBGL_debug_renderer_info").ok().flatten();
 
@@ -437,12 +915,129 @@ fn get_webgl1_info(
         })
         .unwrap_or_default();
 
-    Ok((vendor, renderer, version, shading_version, extensions))
+    let parameters = collect_webgl1_parameters(&gl);
+
+    Ok((
+        vendor,
+        renderer,
+        version,
+        shading_version,
+        extensions,
+        parameters,
+    ))
+}
+
+/// Format a `getParameter` result into a stable string. Handles the scalar
+/// number/boolean cases as well as the typed arrays returned for range
+/// parameters such as `ALIASED_LINE_WIDTH_RANGE` and `MAX_VIEWPORT_DIMS`.
+fn webgl_param_to_string(value: &JsValue) -> String {
+    if let Some(n) = value.as_f64() {
+        return n.to_string();
+    }
+    if let Some(b) = value.as_bool() {
+        return b.to_string();
+    }
+    // Range parameters come back as typed arrays (Int32Array for
+    // MAX_VIEWPORT_DIMS, Float32Array for the ALIASED_* ranges). `Array.from`
+    // iterates those; a plain `dyn_into::<Array>` would not, as they are not
+    // `instanceof Array`.
+    if value.is_object() && !value.is_null() {
+        let arr = js_sys::Array::from(value);
+        if arr.length() > 0 {
+            return (0..arr.length())
+                .map(|i| {
+                    arr.get(i)
+                        .as_f64()
+                        .map(|n| n.to_string())
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+        }
+    }
+    value.as_string().unwrap_or_default()
+}
+
+/// Enumerate the numeric/boolean GL state and per-shader precision formats that
+/// vary by GPU and driver. These meaningfully increase the entropy of a WebGL
+/// fingerprint beyond vendor/renderer strings alone.
+fn collect_webgl1_parameters(gl: &WebGlRenderingContext) -> Vec<(String, String)> {
+    obfuscate_flow!();
+    use WebGlRenderingContext as Gl;
+
+    let numeric: &[(&str, u32)] = &[
+        ("MAX_TEXTURE_SIZE", Gl::MAX_TEXTURE_SIZE),
+        ("MAX_VERTEX_ATTRIBS", Gl::MAX_VERTEX_ATTRIBS),
+        ("MAX_VARYING_VECTORS", Gl::MAX_VARYING_VECTORS),
+        ("MAX_RENDERBUFFER_SIZE", Gl::MAX_RENDERBUFFER_SIZE),
+        ("MAX_VIEWPORT_DIMS", Gl::MAX_VIEWPORT_DIMS),
+        ("ALIASED_LINE_WIDTH_RANGE", Gl::ALIASED_LINE_WIDTH_RANGE),
+        ("ALIASED_POINT_SIZE_RANGE", Gl::ALIASED_POINT_SIZE_RANGE),
+        ("RED_BITS", Gl::RED_BITS),
+        ("GREEN_BITS", Gl::GREEN_BITS),
+        ("BLUE_BITS", Gl::BLUE_BITS),
+        ("ALPHA_BITS", Gl::ALPHA_BITS),
+        ("DEPTH_BITS", Gl::DEPTH_BITS),
+        ("STENCIL_BITS", Gl::STENCIL_BITS),
+        ("MAX_CUBE_MAP_TEXTURE_SIZE", Gl::MAX_CUBE_MAP_TEXTURE_SIZE),
+        ("MAX_TEXTURE_IMAGE_UNITS", Gl::MAX_TEXTURE_IMAGE_UNITS),
+        (
+            "MAX_VERTEX_TEXTURE_IMAGE_UNITS",
+            Gl::MAX_VERTEX_TEXTURE_IMAGE_UNITS,
+        ),
+        (
+            "MAX_COMBINED_TEXTURE_IMAGE_UNITS",
+            Gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS,
+        ),
+        ("MAX_VERTEX_UNIFORM_VECTORS", Gl::MAX_VERTEX_UNIFORM_VECTORS),
+        (
+            "MAX_FRAGMENT_UNIFORM_VECTORS",
+            Gl::MAX_FRAGMENT_UNIFORM_VECTORS,
+        ),
+    ];
+
+    let mut params = Vec::new();
+    for (name, enum_value) in numeric {
+        if let Ok(value) = gl.get_parameter(*enum_value) {
+            params.push((name.to_string(), webgl_param_to_string(&value)));
+        }
+    }
+
+    // Shader precision formats for each shader/precision combination.
+    let shaders: &[(&str, u32)] = &[
+        ("VERTEX", Gl::VERTEX_SHADER),
+        ("FRAGMENT", Gl::FRAGMENT_SHADER),
+    ];
+    let precisions: &[(&str, u32)] = &[
+        ("LOW_FLOAT", Gl::LOW_FLOAT),
+        ("MEDIUM_FLOAT", Gl::MEDIUM_FLOAT),
+        ("HIGH_FLOAT", Gl::HIGH_FLOAT),
+        ("LOW_INT", Gl::LOW_INT),
+        ("MEDIUM_INT", Gl::MEDIUM_INT),
+        ("HIGH_INT", Gl::HIGH_INT),
+    ];
+    for (shader_name, shader_type) in shaders {
+        for (precision_name, precision_type) in precisions {
+            if let Some(format) = gl.get_shader_precision_format(*shader_type, *precision_type) {
+                params.push((
+                    format!("{}_{}", shader_name, precision_name),
+                    format!(
+                        "{},{},{}",
+                        format.range_min(),
+                        format.range_max(),
+                        format.precision()
+                    ),
+                ));
+            }
+        }
+    }
+
+    params
 }
 
 fn get_webgl_info_via_reflection(
     gl: JsValue,
-) -> Result<(String, String, String, String, Vec<String>), JsValue> {
+) -> Result<WebGlInfo, JsValue> {
     obfuscate_flow!();
     // WebGL constants
     const VENDOR: u32 = 0x1F00;
@@ -507,7 +1102,104 @@ fn get_webgl_info_via_reflection(
         vec![]
     };
 
-    Ok((vendor, renderer, version, shading_version, extensions))
+    let parameters = collect_webgl_parameters_via_reflection(&gl, &get_parameter_fn);
+
+    Ok((
+        vendor,
+        renderer,
+        version,
+        shading_version,
+        extensions,
+        parameters,
+    ))
+}
+
+/// Reflection-based counterpart to [`collect_webgl1_parameters`] for contexts
+/// reached only as a raw `JsValue` (e.g. WebGL2). Enumerates the same numeric
+/// state and per-shader precision formats through the context's own methods.
+fn collect_webgl_parameters_via_reflection(
+    gl: &JsValue,
+    get_parameter_fn: &js_sys::Function,
+) -> Vec<(String, String)> {
+    obfuscate_flow!();
+    let numeric: &[(&str, u32)] = &[
+        ("MAX_TEXTURE_SIZE", 0x0D33),
+        ("MAX_VERTEX_ATTRIBS", 0x8869),
+        ("MAX_VARYING_VECTORS", 0x8DFC),
+        ("MAX_RENDERBUFFER_SIZE", 0x84E8),
+        ("MAX_VIEWPORT_DIMS", 0x0D3A),
+        ("ALIASED_LINE_WIDTH_RANGE", 0x846E),
+        ("ALIASED_POINT_SIZE_RANGE", 0x846D),
+        ("RED_BITS", 0x0D52),
+        ("GREEN_BITS", 0x0D53),
+        ("BLUE_BITS", 0x0D54),
+        ("ALPHA_BITS", 0x0D55),
+        ("DEPTH_BITS", 0x0D56),
+        ("STENCIL_BITS", 0x0D57),
+        ("MAX_CUBE_MAP_TEXTURE_SIZE", 0x851C),
+        ("MAX_TEXTURE_IMAGE_UNITS", 0x8872),
+        ("MAX_VERTEX_TEXTURE_IMAGE_UNITS", 0x8B4C),
+        ("MAX_COMBINED_TEXTURE_IMAGE_UNITS", 0x8B4D),
+        ("MAX_VERTEX_UNIFORM_VECTORS", 0x8DFB),
+        ("MAX_FRAGMENT_UNIFORM_VECTORS", 0x8DFD),
+    ];
+
+    let mut params = Vec::new();
+    for (name, enum_value) in numeric {
+        if let Ok(value) = get_parameter_fn.call1(gl, &JsValue::from_f64(*enum_value as f64)) {
+            params.push((name.to_string(), webgl_param_to_string(&value)));
+        }
+    }
+
+    // getShaderPrecisionFormat(shaderType, precisionType) -> { rangeMin, rangeMax, precision }
+    let get_format = js_sys::Reflect::get(
+        gl,
+        &JsValue::from_str(&obfuscate_string!("getShaderPrecisionFormat")),
+    )
+    .ok()
+    .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+
+    if let Some(get_format_fn) = get_format {
+        let shaders: &[(&str, u32)] = &[("VERTEX", 0x8B31), ("FRAGMENT", 0x8B30)];
+        let precisions: &[(&str, u32)] = &[
+            ("LOW_FLOAT", 0x8DF0),
+            ("MEDIUM_FLOAT", 0x8DF1),
+            ("HIGH_FLOAT", 0x8DF2),
+            ("LOW_INT", 0x8DF3),
+            ("MEDIUM_INT", 0x8DF4),
+            ("HIGH_INT", 0x8DF5),
+        ];
+        for (shader_name, shader_type) in shaders {
+            for (precision_name, precision_type) in precisions {
+                if let Ok(format) = get_format_fn.call2(
+                    gl,
+                    &JsValue::from_f64(*shader_type as f64),
+                    &JsValue::from_f64(*precision_type as f64),
+                ) {
+                    if format.is_null() || format.is_undefined() {
+                        continue;
+                    }
+                    let read = |key: &str| {
+                        js_sys::Reflect::get(&format, &JsValue::from_str(key))
+                            .ok()
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or_default()
+                    };
+                    params.push((
+                        format!("{}_{}", shader_name, precision_name),
+                        format!(
+                            "{},{},{}",
+                            read("rangeMin"),
+                            read("rangeMax"),
+                            read("precision")
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    params
 }
 
 fn get_plugins(navigator: &web_sys::Navigator) -> Vec<String> {
@@ -540,24 +1232,74 @@ fn get_mime_types(navigator: &web_sys::Navigator) -> Vec<String> {
     mime_types
 }
 
-fn generate_hash(fingerprint: &Fingerprint) -> String {
-    let mut hasher = Sha256::new();
+/// Render a single component into its canonical `key=value` form. Surfaces
+/// flagged as noisy collapse to an empty value so per-read randomization cannot
+/// poison the hash. Always returns `Some`: an empty value still emits its key.
+fn canonicalize_component(fingerprint: &Fingerprint, component: HashComponent) -> Option<String> {
+    let value = match component {
+        HashComponent::UserAgent => fingerprint.user_agent.clone(),
+        HashComponent::Language => fingerprint.language.clone(),
+        HashComponent::Platform => fingerprint.platform.clone(),
+        HashComponent::HardwareConcurrency => {
+            fingerprint.hardware_concurrency.unwrap_or(0).to_string()
+        }
+        HashComponent::Screen => format!(
+            "{}x{}x{}",
+            fingerprint.screen_width, fingerprint.screen_height, fingerprint.screen_color_depth
+        ),
+        HashComponent::AvailScreen => format!(
+            "{}x{}",
+            fingerprint.screen_avail_width, fingerprint.screen_avail_height
+        ),
+        HashComponent::DevicePixelRatio => fingerprint.device_pixel_ratio.to_string(),
+        HashComponent::Timezone => fingerprint.timezone.clone(),
+        HashComponent::TimezoneOffset => fingerprint.timezone_offset.to_string(),
+        HashComponent::Online => fingerprint.online.to_string(),
+        HashComponent::Canvas => {
+            if fingerprint.noise.canvas_noise {
+                String::new()
+            } else {
+                fingerprint.canvas_fingerprint.clone()
+            }
+        }
+        HashComponent::WebglVendor => fingerprint.webgl_vendor.clone(),
+        HashComponent::WebglRenderer => fingerprint.webgl_renderer.clone(),
+        HashComponent::WebglParameters => fingerprint
+            .webgl_parameters
+            .iter()
+            .map(|(name, value)| format!("{}:{}", name, value))
+            .collect::<Vec<_>>()
+            .join(","),
+        HashComponent::Fonts => fingerprint.fonts.join(","),
+        HashComponent::Audio => {
+            if fingerprint.noise.audio_noise {
+                String::new()
+            } else {
+                fingerprint.audio_fingerprint.clone()
+            }
+        }
+    };
 
-    // Hash all the fingerprint components
-    hasher.update(&fingerprint.user_agent);
-    hasher.update(&fingerprint.language);
-    hasher.update(&fingerprint.platform);
-    hasher.update(fingerprint.hardware_concurrency.unwrap_or(0).to_string());
-    hasher.update(fingerprint.screen_width.to_string());
-    hasher.update(fingerprint.screen_height.to_string());
-    hasher.update(fingerprint.screen_color_depth.to_string());
-    hasher.update(fingerprint.device_pixel_ratio.to_string());
-    hasher.update(&fingerprint.timezone);
-    hasher.update(fingerprint.timezone_offset.to_string());
-    hasher.update(&fingerprint.canvas_fingerprint);
-    hasher.update(&fingerprint.webgl_vendor);
-    hasher.update(&fingerprint.webgl_renderer);
+    let key = format!("{:?}", component);
+    Some(format!("{}={}", key, value))
+}
 
-    let result = hasher.finalize();
-    hex::encode(result)
+/// Serialize the selected components in the fixed [`COMPONENT_ORDER`] into a
+/// normalized, version-tolerant string. Walking a fixed order means toggling a
+/// component on/off never reshuffles the others.
+fn canonicalize(fingerprint: &Fingerprint, components: &[HashComponent]) -> String {
+    COMPONENT_ORDER
+        .iter()
+        .filter(|component| components.contains(*component))
+        .filter_map(|component| canonicalize_component(fingerprint, *component))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Canonicalize the configured components and hash the result.
+fn compute_hash(fingerprint: &Fingerprint, config: &HashConfig) -> String {
+    let canonical = canonicalize(fingerprint, &config.resolved_components());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.as_bytes());
+    hex::encode(hasher.finalize())
 }